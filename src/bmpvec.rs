@@ -89,7 +89,7 @@ unsafe impl<'t, T: Sync> Sync for BmpSlice<'t, T> {}
 ///
 pub trait ReadBmpVec<'t, T, Ptr> {
     /// Borrow a [`BmpVec`] as a read-only [`BmpSlice`]
-    fn borrow(&self) -> BmpSlice<T> {
+    fn borrow(&self) -> BmpSlice<'_, T> {
         let (bmp, slice) = self.as_cooked_parts();
         BmpSlice::from_cooked_parts(bmp, slice)
     }
@@ -114,13 +114,13 @@ pub trait ReadBmpVec<'t, T, Ptr> {
     }
 
     /// An iterator visiting each element in the `BmpVec` or `BmpSlice`.
-    fn values(&self) -> std::slice::Iter<T> {
+    fn values(&self) -> std::slice::Iter<'_, T> {
         self.as_cooked_parts().1.iter()
     }
 
     /// An iterator visiting the position and value of each element in the
     /// `BmpVec` or `BmpSlice`.
-    fn iter(&self) -> Iter<T> {
+    fn iter(&self) -> Iter<'_, T> {
         Iter { keys: self.keys(), vals: self.values() }
     }
 
@@ -131,7 +131,7 @@ pub trait ReadBmpVec<'t, T, Ptr> {
         N: TryInto<u8>,
     {
         let (bmp, _) = self.as_cooked_parts();
-        bitmask(pos).map_or(false, |(bit, _)| bmp & bit)
+        bitmask(pos).is_some_and(|(bit, _)| bmp & bit)
     }
 
     /// Get a reference to an element in the `BmpVec`
@@ -232,6 +232,8 @@ macro_rules! impl_read_bmp_vec {
             Self { bmp, ptr, _marker: PhantomData }
         }
 
+        // BmpSlice does not need forgetting but BmpVec does
+        #[allow(clippy::forget_non_drop)]
         unsafe fn into_raw_parts(self) -> (u64, $ptr) {
             let (bmp, ptr) = (self.bmp.into_raw_parts(), self.ptr);
             std::mem::forget(self); // avoid double free
@@ -256,7 +258,7 @@ impl<'t, T> BmpSlice<'t, T> {
     /// Panics if the number of bits set in the bitmap is not the same as the
     /// length of the slice.
     ///
-    fn from_cooked_parts(bmp: Bmp, slice: &[T]) -> BmpSlice<T> {
+    fn from_cooked_parts(bmp: Bmp, slice: &[T]) -> BmpSlice<'_, T> {
         assert_eq!(bmp.len(), slice.len());
         let ptr = slice.as_ptr();
         BmpSlice { bmp, ptr, _marker: PhantomData }
@@ -490,7 +492,7 @@ where
 ///   * `Bmp & Bit -> bool` : test whether a `Bit` is present
 ///
 /// The other operator is `Bmp ^ Bit` which is not weird.
-
+///
 mod bmp {
     use core::convert::TryInto;
 
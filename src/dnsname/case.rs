@@ -0,0 +1,245 @@
+//! A case-preserving DNS name
+//! ==========================
+//!
+//! A [`CaseName`] is like a [`HeapName`], except that it keeps the
+//! original spelling of the name, so that it can be written out the
+//! same way it was read in. Comparisons still ignore ASCII case, as
+//! does the key made from it by [`TrieName::from_dns_name()`].
+//!
+//! A `CaseName` is not a [`DnsName`], because its labels are not
+//! squashed to lower case.
+//!
+//! A `CaseName` with random case can be used for DNS 0x20 spoofing
+//! resistance, checking the reply with [`eq_case_sensitive()`].
+
+use crate::prelude::*;
+use std::hash::{Hash, Hasher};
+
+/// A case-preserving DNS name owned and allocated on the heap
+///
+/// It has the same layout as a [`HeapName`].
+///
+pub struct CaseName {
+    heap: HeapName,
+}
+
+impl DnsLabels for CaseName {
+    fn labs(&self) -> usize {
+        self.heap.labs()
+    }
+
+    fn nlen(&self) -> usize {
+        self.heap.nlen()
+    }
+
+    fn label(&self, lab: usize) -> Option<&[u8]> {
+        DnsName::label(&self.heap, lab)
+    }
+}
+
+impl CaseName {
+    /// Copy any kind of name, keeping its case
+    ///
+    /// Fails with `NameSyntax` if the name is empty.
+    ///
+    pub fn from_labels<N>(name: &N) -> Result<CaseName>
+    where
+        N: DnsLabels,
+    {
+        if name.labs() == 0 {
+            return Err(NameSyntax);
+        }
+        Ok(CaseName { heap: HeapName::from_labels_with(name, |&ch| ch) })
+    }
+
+    /// Copy any kind of name, randomizing the case of its letters
+    /// for DNS 0x20 (draft-vixie-dnsext-dns0x20).
+    ///
+    /// Each call to `rng` must return 64 random bits. Fails with
+    /// `NameSyntax` if the name is empty.
+    ///
+    pub fn random_case<N, R>(name: &N, mut rng: R) -> Result<CaseName>
+    where
+        N: DnsLabels,
+        R: FnMut() -> u64,
+    {
+        if name.labs() == 0 {
+            return Err(NameSyntax);
+        }
+        let mut bits = 0;
+        let mut count = 0;
+        let heap = HeapName::from_labels_with(name, |&ch| {
+            if !ch.is_ascii_alphabetic() {
+                return ch;
+            }
+            if count == 0 {
+                bits = rng();
+                count = 64;
+            }
+            let flip = bits & 1 == 1;
+            bits >>= 1;
+            count -= 1;
+            if flip {
+                ch.to_ascii_uppercase()
+            } else {
+                ch.to_ascii_lowercase()
+            }
+        });
+        Ok(CaseName { heap })
+    }
+
+    /// The name in uncompressed wire format, in its original case
+    ///
+    pub fn wire(&self) -> &[u8] {
+        self.heap.name()
+    }
+}
+
+/// Compare two names exactly, without ignoring case.
+///
+/// This is for checking that the question in a response matches a
+/// query sent with [`CaseName::random_case()`].
+///
+pub fn eq_case_sensitive<A, B>(aa: &A, bb: &B) -> bool
+where
+    A: DnsLabels,
+    B: DnsLabels,
+{
+    aa.labs() == bb.labs()
+        && (0..aa.labs()).all(|lab| aa.label(lab) == bb.label(lab))
+}
+
+impl From<&CaseName> for HeapName {
+    fn from(name: &CaseName) -> HeapName {
+        HeapName::from_labels_with(name, u8::to_ascii_lowercase)
+    }
+}
+
+impl<P> TryFrom<&WireLabels<'_, P>> for CaseName
+where
+    P: Copy + TryFrom<usize> + Into<usize>,
+{
+    type Error = Error;
+    fn try_from(wire: &WireLabels<'_, P>) -> Result<CaseName> {
+        CaseName::from_labels(wire)
+    }
+}
+
+impl TryFrom<&[u8]> for CaseName {
+    type Error = Error;
+    fn try_from(wire: &[u8]) -> Result<CaseName> {
+        let mut labels = WireLabels::<u8>::new();
+        labels.from_wire(wire, 0)?;
+        CaseName::from_labels(&labels)
+    }
+}
+
+impl TryFrom<&str> for CaseName {
+    type Error = Error;
+    fn try_from(text: &str) -> Result<CaseName> {
+        let mut scratch = ScratchName::new();
+        let end = scratch.parse_text_keep_case(text.as_bytes())?;
+        if end == text.len() {
+            Ok(CaseName { heap: HeapName::from(scratch) })
+        } else {
+            Err(NameTrailing)
+        }
+    }
+}
+
+impl Eq for CaseName {}
+
+impl<Other: DnsLabels> PartialEq<Other> for CaseName {
+    fn eq(&self, other: &Other) -> bool {
+        cmp_any_names(self, other) == Ordering::Equal
+    }
+}
+
+impl Ord for CaseName {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_any_names(self, other)
+    }
+}
+
+impl<Other: DnsLabels> PartialOrd<Other> for CaseName {
+    fn partial_cmp(&self, other: &Other) -> Option<Ordering> {
+        Some(cmp_any_names(self, other))
+    }
+}
+
+impl Hash for CaseName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_any_name(self, state)
+    }
+}
+
+impl std::fmt::Display for CaseName {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.to_text(f)
+    }
+}
+
+impl std::fmt::Debug for CaseName {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("CaseName")
+            .field("lpos", &self.heap.lpos())
+            .field("name", &self.heap.name())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::dnsname::hash;
+
+    #[test]
+    fn test() -> Result<()> {
+        let text = "WWW.Dotat.AT";
+        let name = CaseName::try_from(text)?;
+        assert_eq!(text, format!("{}", name));
+        assert_eq!(name.wire(), b"\x03WWW\x05Dotat\x02AT\x00");
+        let lower = CaseName::try_from("www.dotat.at.")?;
+        assert_eq!(name, lower);
+        assert_eq!(hash(&name), hash(&lower));
+        let heap = HeapName::from(&name);
+        assert_eq!("www.dotat.at", format!("{}", heap));
+        assert_eq!(name, heap);
+        let mut upper = TrieName::new();
+        upper.from_dns_name(&name);
+        let mut lower = TrieName::new();
+        lower.from_dns_name(&heap);
+        assert_eq!(upper.as_slice(), lower.as_slice());
+        let root = CaseName::try_from(".")?;
+        assert_eq!(".", format!("{}", root));
+        let esc = CaseName::try_from("A\\.B.\\067")?;
+        assert_eq!("A\\.B.C", format!("{}", esc));
+        assert!(CaseName::try_from("a..b").is_err());
+        let empty = ScratchName::new();
+        assert!(matches!(CaseName::from_labels(&empty), Err(NameSyntax)));
+        let empty = WireLabels::<u8>::new();
+        assert!(matches!(CaseName::try_from(&empty), Err(NameSyntax)));
+        Ok(())
+    }
+
+    #[test]
+    fn dns0x20() -> Result<()> {
+        let name = HeapName::try_from("www.dotat.at")?;
+        let pattern = CaseName::random_case(&name, || 0b1010_0110)?;
+        assert_eq!("wWW.doTaT.at", format!("{}", pattern));
+        let query = CaseName::random_case(&name, || fastrand::u64(..))?;
+        let wire = query.wire().to_vec();
+        let mut reply = WireLabels::<u8>::new();
+        reply.from_wire(&wire, 0)?;
+        assert!(eq_case_sensitive(&query, &reply));
+        assert_eq!(reply, name);
+        let spoof = CaseName::try_from("WWW.DOTAT.AT")?;
+        assert!(!eq_case_sensitive(&pattern, &spoof));
+        assert!(eq_case_sensitive(&pattern, &pattern));
+        assert_eq!(pattern, spoof);
+        let empty = ScratchName::new();
+        let random = CaseName::random_case(&empty, || fastrand::u64(..));
+        assert!(matches!(random, Err(NameSyntax)));
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+//! Comparing any kind of DNS name
+//! ===============================
+//!
+//! These functions work on any [`DnsLabels`] name, so they cannot
+//! assume the labels have been squashed to lower case. Names that
+//! are known to be in lower case can use [`DnsName::cmp_names()`].
+
+use crate::prelude::*;
+
+fn cmp_any_labels(aa: &[u8], bb: &[u8]) -> Ordering {
+    for chr in 0.. {
+        let a = &aa.get(chr).map(|a| a.to_ascii_lowercase());
+        let b = &bb.get(chr).map(|b| b.to_ascii_lowercase());
+        match a.cmp(b) {
+            Ordering::Equal if a.is_none() && b.is_none() => break,
+            Ordering::Equal => continue,
+            ne => return ne,
+        }
+    }
+    Ordering::Equal
+}
+
+/// How two DNS names are related in the DNS hierarchy
+///
+/// Returned by [`full_compare()`] in the [`FullCompare::relation`]
+/// field. Like BIND's `dns_namereln_t`.
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NameRelation {
+    /// The names are the same, ignoring ASCII case
+    Equal,
+    /// The first name is below the second name
+    Subdomain,
+    /// The first name is above the second name
+    Superdomain,
+    /// The names share an ancestor below the root
+    CommonAncestor,
+    /// The names have nothing in common except the root
+    Unrelated,
+}
+
+/// The result of [`full_compare()`]
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FullCompare {
+    /// The canonical DNS name order (RFC 4034 section 6.1)
+    pub order: Ordering,
+    /// The number of labels the names have in common, counting from
+    /// the root, so it is at least 1
+    pub labs: usize,
+    /// How the names are related in the DNS hierarchy
+    pub relation: NameRelation,
+}
+
+/// Compare any two DNS names, ignoring ASCII case, and work out how
+/// they are related. Like BIND's `dns_name_fullcompare()`.
+///
+pub fn full_compare<A, B>(aa: &A, bb: &B) -> FullCompare
+where
+    A: DnsLabels + ?Sized,
+    B: DnsLabels + ?Sized,
+{
+    let mut labs = 0;
+    let order = loop {
+        match (aa.rlabel(labs), bb.rlabel(labs)) {
+            (None, None) => break Ordering::Equal,
+            (None, Some(_)) => break Ordering::Less,
+            (Some(_), None) => break Ordering::Greater,
+            (Some(a), Some(b)) => match cmp_any_labels(a, b) {
+                Ordering::Equal => labs += 1,
+                ne => break ne,
+            },
+        }
+    };
+    let relation = match order {
+        Ordering::Equal => NameRelation::Equal,
+        _ if labs == bb.labs() => NameRelation::Subdomain,
+        _ if labs == aa.labs() => NameRelation::Superdomain,
+        _ if labs > 1 => NameRelation::CommonAncestor,
+        _ => NameRelation::Unrelated,
+    };
+    FullCompare { order, labs, relation }
+}
+
+pub(crate) fn cmp_any_names<A, B>(aa: &A, bb: &B) -> Ordering
+where
+    A: DnsLabels,
+    B: DnsLabels,
+{
+    full_compare(aa, bb).order
+}
+
+/// Hash any kind of name consistently with [`cmp_any_names()`], by
+/// hashing its uncompressed wire format in lower case.
+///
+pub(crate) fn hash_any_name<N, H>(name: &N, state: &mut H)
+where
+    N: DnsLabels,
+    H: std::hash::Hasher,
+{
+    let mut wire = ArrayVec::<u8, MAX_NAME>::new();
+    for lab in 0..name.labs() {
+        let label = name.label(lab).unwrap_or_default();
+        wire.push(label.len() as u8);
+        wire.extend(label.iter().map(u8::to_ascii_lowercase));
+    }
+    state.write(&wire);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn full() -> Result<()> {
+        let wire = b"\x03WWW\x05Dotat\x02AT\x00";
+        let mut www = WireLabels::<u8>::new();
+        www.from_wire(wire, 0)?;
+        let check = |text, order, labs, relation| -> Result<()> {
+            let name = HeapName::try_from(text)?;
+            let full = FullCompare { order, labs, relation };
+            assert_eq!(full_compare(&www, &name), full);
+            Ok(())
+        };
+        use NameRelation as Rel;
+        use Ordering::*;
+        check("www.dotat.at", Equal, 4, Rel::Equal)?;
+        check("dotat.at", Greater, 3, Rel::Subdomain)?;
+        check(".", Greater, 1, Rel::Subdomain)?;
+        check("a.www.dotat.at", Less, 4, Rel::Superdomain)?;
+        check("mail.dotat.at", Greater, 3, Rel::CommonAncestor)?;
+        check("zzz.dotat.at", Less, 3, Rel::CommonAncestor)?;
+        check("dotat.org", Less, 1, Rel::Unrelated)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+//! Writing names into DNS messages
+//! ===============================
+//!
+//! A [`Compressor`] writes names into a DNS message being built,
+//! replacing suffixes that have already been written with compression
+//! pointers (RFC 1035 section 4.1.4). Pointers are 14 bits, so only
+//! names within the first 16 KiB of the message can be targets.
+//!
+//! Names in the RDATA of some RR types must not be compressed (RFC
+//! 3597 section 4); they are written by [`write_name_uncompressed()`].
+//!
+//! The compressor only remembers where each name starts. The
+//! positions of its suffixes are found by re-parsing the name from
+//! the message using [`WireLabels`], which also follows any pointers
+//! that were written for it.
+
+use crate::prelude::*;
+
+/// Compression pointers have 14 bits of offset
+const MAX_POINTER: usize = 0x3FFF;
+
+/// Write a DNS `name` into a `msg` without compression.
+///
+pub fn write_name_uncompressed<N>(msg: &mut Vec<u8>, name: &N)
+where
+    N: DnsLabels,
+{
+    for lab in 0..name.labs() {
+        write_label(msg, name.label(lab).unwrap());
+    }
+}
+
+fn write_label(msg: &mut Vec<u8>, label: &[u8]) {
+    msg.push(label.len() as u8);
+    msg.extend_from_slice(label);
+}
+
+/// Remembers the names written into a DNS message, for compression.
+///
+/// A `Compressor` should be used with only one message, and the
+/// message must not be truncated below the end of any name that has
+/// been written by the compressor.
+///
+#[derive(Debug, Default)]
+pub struct Compressor {
+    starts: Vec<usize>,
+}
+
+impl Compressor {
+    pub fn new() -> Self {
+        Compressor { starts: Vec::new() }
+    }
+
+    /// Forget all the names, so the `Compressor` can be used with a
+    /// new message.
+    ///
+    pub fn clear(&mut self) {
+        self.starts.clear();
+    }
+
+    /// Write a DNS `name` into a `msg`, compressing it against the
+    /// names previously written by this `Compressor`.
+    ///
+    /// The name is remembered so that later names can point to it.
+    ///
+    pub fn write_name<N>(&mut self, msg: &mut Vec<u8>, name: &N) -> Result<()>
+    where
+        N: DnsLabels,
+    {
+        let start = msg.len();
+        let labs = name.labs();
+        // the root is too short to be worth compressing
+        let mut best_labs = 1;
+        let mut best_pos = None;
+        for &prev_start in self.starts.iter() {
+            let mut prev = WireLabels::<u16>::new();
+            prev.from_wire(msg, prev_start)?;
+            let common = full_compare(name, &prev).labs;
+            if common > best_labs {
+                let pos = prev.label_pos(prev.labs() - common);
+                if pos <= MAX_POINTER {
+                    best_labs = common;
+                    best_pos = Some(pos);
+                }
+            }
+        }
+        match best_pos {
+            None => write_name_uncompressed(msg, name),
+            Some(pos) => {
+                for lab in 0..labs - best_labs {
+                    write_label(msg, name.label(lab).unwrap());
+                }
+                msg.push(0xC0 | (pos >> 8) as u8);
+                msg.push((pos & 0xFF) as u8);
+            }
+        }
+        if start <= MAX_POINTER && best_labs < labs {
+            self.starts.push(start);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() -> Result<()> {
+        let texts = ["www.dotat.at", "mail.dotat.at", "dotat.at", "dotat.org"];
+        let mut names = Vec::new();
+        for text in texts.iter() {
+            names.push(HeapName::try_from(*text)?);
+        }
+        let mut msg = vec![0; 12];
+        let mut starts = Vec::new();
+        let mut compressor = Compressor::new();
+        for name in names.iter() {
+            starts.push(msg.len());
+            compressor.write_name(&mut msg, name)?;
+        }
+        let wire = b"\x03www\x05dotat\x02at\x00\
+                     \x04mail\xC0\x10\
+                     \xC0\x10\
+                     \x05dotat\x03org\x00";
+        assert_eq!(&msg[12..], &wire[..]);
+        for (name, &start) in names.iter().zip(starts.iter()) {
+            let mut scratch = ScratchName::new();
+            scratch.from_wire(&msg, start)?;
+            assert_eq!(&scratch, name);
+        }
+        // too far away for a pointer
+        let mut msg = vec![0; MAX_POINTER + 1];
+        let mut compressor = Compressor::new();
+        let start = msg.len();
+        compressor.write_name(&mut msg, &names[0])?;
+        compressor.write_name(&mut msg, &names[0])?;
+        assert_eq!(&msg[start..start + 14], &msg[start + 14..]);
+        // forbidden
+        let mut msg = Vec::new();
+        write_name_uncompressed(&mut msg, &names[0]);
+        write_name_uncompressed(&mut msg, &names[0]);
+        assert_eq!(&msg[..14], &msg[14..]);
+        Ok(())
+    }
+}
@@ -79,6 +79,108 @@ impl HeapName {
     }
 }
 
+impl HeapName {
+    /// Make a new name by adding a `label` to the start of a `name`.
+    ///
+    pub fn prepend_label<N>(label: &[u8], name: &N) -> Result<HeapName>
+    where
+        N: DnsLabels,
+    {
+        let mut scratch = ScratchName::new();
+        scratch.from_labels(name)?;
+        scratch.prepend_label(label)?;
+        Ok(scratch.into())
+    }
+
+    /// Make a new name from the `left` name (without its root label)
+    /// followed by the `right` name.
+    ///
+    pub fn concat<A, B>(left: &A, right: &B) -> Result<HeapName>
+    where
+        A: DnsLabels,
+        B: DnsLabels,
+    {
+        let mut scratch = ScratchName::new();
+        scratch.concat(left, right)?;
+        Ok(scratch.into())
+    }
+
+    /// Parse a name in presentation format, failing if it does not
+    /// have a trailing dot.
+    ///
+    pub fn from_text_absolute(text: &str) -> Result<HeapName> {
+        let mut scratch = ScratchName::new();
+        let end = scratch.from_text_absolute(text.as_bytes())?;
+        HeapName::from_whole_text(scratch, end, text)
+    }
+
+    /// Parse a name in presentation format, as in a zone file.
+    ///
+    /// A name without a trailing dot is relative to the `origin`,
+    /// and `@` on its own means the `origin` itself.
+    ///
+    pub fn from_text_origin<N>(text: &str, origin: &N) -> Result<HeapName>
+    where
+        N: DnsLabels,
+    {
+        let mut scratch = ScratchName::new();
+        let end = scratch.from_text_origin(text.as_bytes(), origin)?;
+        HeapName::from_whole_text(scratch, end, text)
+    }
+
+    /// Copy any kind of name, passing each byte of its labels
+    /// through the `fold` function.
+    ///
+    /// The name is not necessarily in lower case afterwards, so
+    /// this is only for use by [`HeapName`] and [`CaseName`].
+    ///
+    /// # Panics
+    ///
+    /// The name must not be empty, because an empty name has no
+    /// root label and cannot satisfy the layout described at
+    /// [`HeapName`] under "Safety". Callers that accept arbitrary
+    /// names must check first.
+    ///
+    pub(crate) fn from_labels_with<N, F>(name: &N, mut fold: F) -> HeapName
+    where
+        N: DnsLabels,
+        F: FnMut(&u8) -> u8,
+    {
+        assert!(name.labs() > 0, "cannot copy an empty name");
+        let mut vec = vec![0; name.heap_len()];
+        let labs = name.labs();
+        vec[0] = labs as u8;
+        let mut lpos = 0;
+        let mut npos = labs + 1;
+        for lab in 0..labs {
+            let label = name.label(lab).unwrap();
+            let llen = label.len() as u8;
+            vec[lab + 1] = lpos;
+            lpos += 1 + llen;
+            vec[npos] = llen;
+            npos += 1;
+            for ch in label {
+                vec[npos] = fold(ch);
+                npos += 1;
+            }
+        }
+        // SAFETY: see [`HeapName`] under "Safety"
+        unsafe { HeapName::from_vec(vec) }
+    }
+
+    fn from_whole_text(
+        scratch: ScratchName,
+        end: usize,
+        text: &str,
+    ) -> Result<HeapName> {
+        if end == text.len() {
+            Ok(scratch.into())
+        } else {
+            Err(NameTrailing)
+        }
+    }
+}
+
 impl Drop for HeapName {
     fn drop(&mut self) {
         let ptr = self.mem as *mut u8;
@@ -174,25 +276,7 @@ where
     P: Copy + TryFrom<usize> + Into<usize>,
 {
     fn from(wire: &WireLabels<'_, P>) -> HeapName {
-        let mut vec = vec![0; wire.heap_len()];
-        let labs = wire.labs();
-        vec[0] = labs as u8;
-        let mut lpos = 0;
-        let mut npos = labs + 1;
-        for lab in 0..labs {
-            let label = wire.label(lab).unwrap();
-            let llen = label.len() as u8;
-            vec[lab + 1] = lpos;
-            lpos += 1 + llen;
-            vec[npos] = llen;
-            npos += 1;
-            for ch in label {
-                vec[npos] = ch.to_ascii_lowercase();
-                npos += 1;
-            }
-        }
-        // SAFETY: see [`HeapName`] under "Safety"
-        unsafe { HeapName::from_vec(vec) }
+        HeapName::from_labels_with(wire, u8::to_ascii_lowercase)
     }
 }
 
@@ -205,6 +289,24 @@ where
     }
 }
 
+impl<N> From<&Suffix<'_, N>> for HeapName
+where
+    N: DnsLabels,
+{
+    fn from(suffix: &Suffix<'_, N>) -> HeapName {
+        HeapName::from_labels_with(suffix, u8::to_ascii_lowercase)
+    }
+}
+
+impl<N> From<Suffix<'_, N>> for HeapName
+where
+    N: DnsLabels,
+{
+    fn from(suffix: Suffix<'_, N>) -> HeapName {
+        HeapName::from(&suffix)
+    }
+}
+
 impl TryFrom<&[u8]> for HeapName {
     type Error = Error;
     fn try_from(wire: &[u8]) -> Result<HeapName> {
@@ -219,10 +321,73 @@ impl TryFrom<&str> for HeapName {
     fn try_from(text: &str) -> Result<HeapName> {
         let mut scratch = ScratchName::new();
         let end = scratch.from_text(text.as_bytes())?;
-        if end == text.len() {
-            Ok(scratch.into())
+        HeapName::from_whole_text(scratch, end, text)
+    }
+}
+
+/// Serialize a [`HeapName`] as presentation-format text for
+/// human-readable formats, or as uncompressed wire format otherwise.
+///
+#[cfg(feature = "serde")]
+impl serde::Serialize for HeapName {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
         } else {
-            Err(NameTrailing)
+            serializer.serialize_bytes(self.name())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for HeapName {
+    fn deserialize<D>(
+        deserializer: D,
+    ) -> std::result::Result<HeapName, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(HeapNameVisitor)
+        } else {
+            deserializer.deserialize_bytes(HeapNameVisitor)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+struct HeapNameVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for HeapNameVisitor {
+    type Value = HeapName;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "a DNS name")
+    }
+
+    fn visit_str<E>(self, text: &str) -> std::result::Result<HeapName, E>
+    where
+        E: serde::de::Error,
+    {
+        HeapName::try_from(text).map_err(E::custom)
+    }
+
+    fn visit_bytes<E>(self, wire: &[u8]) -> std::result::Result<HeapName, E>
+    where
+        E: serde::de::Error,
+    {
+        let mut scratch = ScratchName::new();
+        match scratch.from_wire(wire, 0) {
+            Ok(end) if end == wire.len() => Ok(scratch.into()),
+            Ok(_) => Err(E::custom(NameTrailing)),
+            Err(err) => Err(E::custom(err)),
         }
     }
 }
@@ -230,6 +395,7 @@ impl TryFrom<&str> for HeapName {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::test::dnsname::hash;
 
     #[test]
     fn test() -> Result<()> {
@@ -242,4 +408,73 @@ mod test {
         assert_eq!(text, format!("{}", name));
         Ok(())
     }
+
+    #[test]
+    fn hash_map() -> Result<()> {
+        let mut map = std::collections::HashMap::new();
+        map.insert(HeapName::try_from("dotat.at")?, 1);
+        map.insert(HeapName::try_from("www.dotat.at")?, 2);
+        let mut wire = WireLabels::<u8>::new();
+        wire.from_wire(b"\x05DoTaT\x02aT\x00", 0)?;
+        assert_eq!(map.get(&HeapName::from(&wire)), Some(&1));
+        let mut scratch = ScratchName::new();
+        scratch.from_text(b"WWW.dotat.AT")?;
+        assert_eq!(map.get(&HeapName::from(&scratch)), Some(&2));
+        // mixed-case wire labels hash the same as the equal lower
+        // case names, without being copied first
+        let heap = HeapName::try_from("dotat.at")?;
+        let mut lower = ScratchName::new();
+        lower.from_text(b"dotat.at")?;
+        assert_eq!(hash(&wire), hash(&heap));
+        assert_eq!(hash(&wire), hash(&lower));
+        let mut www = WireLabels::<u16>::new();
+        www.from_wire(b"\x03wWw\x05DOtat\x02At\x00", 0)?;
+        assert_eq!(hash(&www), hash(&scratch));
+        assert_eq!(hash(&www), hash(&HeapName::from(&scratch)));
+        assert_eq!(hash(&www.parent().unwrap()), hash(&heap));
+        assert_eq!(map.get(&HeapName::from(www.parent().unwrap())), Some(&1));
+        let mut plain = WireLabels::<u16>::new();
+        plain.from_wire(b"\x05dotat\x02at\x00", 0)?;
+        let mut compressed = WireLabels::<u16>::new();
+        compressed.from_wire(b"\x02AT\x00\x05DOTAT\xC0\x00", 4)?;
+        let mut set = std::collections::HashSet::new();
+        assert!(set.insert(plain));
+        assert!(!set.insert(compressed));
+        Ok(())
+    }
+
+    #[test]
+    fn origin() -> Result<()> {
+        let origin = HeapName::try_from("dotat.at")?;
+        let www = HeapName::from_text_origin("www", &origin)?;
+        assert_eq!("www.dotat.at", format!("{}", www));
+        let www = HeapName::from_text_origin("www.", &origin)?;
+        assert_eq!(www, HeapName::try_from("www")?);
+        let at = HeapName::from_text_origin("@", &origin)?;
+        assert_eq!(at, origin);
+        let at = HeapName::from_text_origin("\\@", &origin)?;
+        assert_eq!("\\@.dotat.at", format!("{}", at));
+        let root = HeapName::from_text_origin(".", &origin)?;
+        assert_eq!(".", format!("{}", root));
+        let dot = HeapName::from_text_origin("www\\.", &origin)?;
+        assert_eq!("www\\..dotat.at", format!("{}", dot));
+        assert!(HeapName::from_text_absolute("dotat.at.").is_ok());
+        assert!(HeapName::from_text_absolute(".").is_ok());
+        let rel = HeapName::from_text_absolute("dotat.at");
+        assert!(matches!(rel, Err(NameRelative)));
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() -> Result<()> {
+        use serde_test::{assert_tokens, Configure, Token};
+        let text = "dotat.at";
+        let name = HeapName::try_from(text)?;
+        assert_tokens(&name.readable(), &[Token::Str(text)]);
+        let wire = b"\x05dotat\x02at\x00";
+        let name = HeapName::try_from(&wire[..])?;
+        assert_tokens(&name.compact(), &[Token::Bytes(wire)]);
+        Ok(())
+    }
 }
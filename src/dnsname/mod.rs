@@ -50,8 +50,13 @@
 use crate::prelude::*;
 use core::cmp::max;
 
+pub use self::case::*;
+pub use self::compare::*;
+pub use self::compress::*;
 pub use self::heap::*;
 pub use self::scratch::*;
+pub use self::suffix::*;
+pub use self::text::*;
 pub use self::wire::*;
 
 /// Maximum length of a DNS name, in octets on the wire.
@@ -105,27 +110,144 @@ pub trait DnsLabels {
         }
     }
 
+    /// The name with its leftmost `count` labels removed.
+    ///
+    /// Returns `None` if that would remove the root label.
+    ///
+    fn ancestor(&self, count: usize) -> Option<Suffix<'_, Self>>
+    where
+        Self: Sized,
+    {
+        Suffix::new(self, count)
+    }
+
+    /// The name with its leftmost label removed.
+    ///
+    /// Returns `None` for the root name.
+    ///
+    fn parent(&self) -> Option<Suffix<'_, Self>>
+    where
+        Self: Sized,
+    {
+        self.ancestor(1)
+    }
+
+    /// The number of labels this name has in common with the `other`
+    /// name, counting from the right and ignoring ASCII case. For
+    /// any two names this is at least 1, for the root.
+    ///
+    fn common_labs<Other>(&self, other: &Other) -> usize
+    where
+        Other: DnsLabels,
+    {
+        full_compare(self, other).labs
+    }
+
+    /// The closest name that this name and the `other` name are both
+    /// subdomains of.
+    ///
+    fn common_ancestor<Other>(&self, other: &Other) -> Suffix<'_, Self>
+    where
+        Self: Sized,
+        Other: DnsLabels,
+    {
+        let skip = self.labs() - self.common_labs(other);
+        Suffix::from_parts(self, skip)
+    }
+
+    /// Returns `true` if this name is the same as the `other` name or
+    /// is below it in the DNS hierarchy, ignoring ASCII case.
+    ///
+    fn is_subdomain_of<Other>(&self, other: &Other) -> bool
+    where
+        Other: DnsLabels,
+    {
+        matches!(
+            full_compare(self, other).relation,
+            NameRelation::Equal | NameRelation::Subdomain
+        )
+    }
+
+    /// The labels of this name to the left of the `origin`, or `None`
+    /// if this name is not a subdomain of the `origin`.
+    ///
+    fn relativize<Other>(&self, origin: &Other) -> Option<Labels<'_, Self>>
+    where
+        Self: Sized,
+        Other: DnsLabels,
+    {
+        if self.is_subdomain_of(origin) {
+            Some(Labels::new(self, 0..self.labs() - origin.labs()))
+        } else {
+            None
+        }
+    }
+
+    /// Write the name in presentation format, using the default
+    /// [`TextFormat`].
+    ///
     fn to_text(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_text(f, &TextFormat::new())
+    }
+
+    /// Write the name in presentation format, as specified by the
+    /// [`TextFormat`].
+    ///
+    fn fmt_text<W, O>(
+        &self,
+        out: &mut W,
+        format: &TextFormat<O>,
+    ) -> std::fmt::Result
+    where
+        W: std::fmt::Write,
+        O: DnsLabels,
+    {
         let labs = self.labs();
-        for lab in 0..labs {
-            let label = self.label(lab).ok_or(std::fmt::Error)?;
-            for &byte in label.iter() {
-                match byte {
-                    b'*' | b'-' | b'_' | // permitted punctuation
-                    b'0'..=b'9' |
-                    b'A'..=b'Z' |
-                    b'a'..=b'z' => write!(f, "{}", byte as char)?,
-                    b'!'..=b'~' => write!(f, "\\{}", byte as char)?,
-                    // RFC 1035 peculiar decimal escapes
-                    _ => write!(f, "\\{:03}", byte)?,
+        let (count, dot) = match format.origin {
+            Some(origin) if self.is_subdomain_of(origin) => {
+                if labs == origin.labs() {
+                    return out.write_char('@');
                 }
+                (labs - origin.labs(), false)
             }
-            if lab == 0 || lab + 2 < labs {
-                write!(f, ".")?;
+            Some(_) => (labs.saturating_sub(1), true),
+            // top-level names get a dot to avoid confusion
+            None => (labs.saturating_sub(1), format.trailing_dot || labs <= 2),
+        };
+        for lab in 0..count {
+            if lab > 0 {
+                out.write_char('.')?;
             }
+            let label = self.label(lab).ok_or(std::fmt::Error)?;
+            format.escape.write_label(out, label)?;
+        }
+        if dot {
+            out.write_char('.')?;
         }
         Ok(())
     }
+
+    /// Write the name in presentation format into a `buf`fer, as
+    /// specified by the [`TextFormat`], without allocating.
+    ///
+    /// Returns the length of the text, or `ScratchOverflow` if the
+    /// buffer is too small. A buffer of [`MAX_TEXT`] bytes is always
+    /// big enough.
+    ///
+    fn write_text<O>(
+        &self,
+        buf: &mut [u8],
+        format: &TextFormat<O>,
+    ) -> Result<usize>
+    where
+        O: DnsLabels,
+    {
+        let mut out = TextBuffer { buf, pos: 0 };
+        match self.fmt_text(&mut out, format) {
+            Ok(()) => Ok(out.pos),
+            Err(_) => Err(ScratchOverflow),
+        }
+    }
 }
 
 /// A DNS name in uncompressed lowercase wire format.
@@ -183,6 +305,14 @@ macro_rules! impl_dns_name {
             }
         }
 
+        // the name is already in lower case, so this is the
+        // same as `hash_any_name()` without the copy
+        impl std::hash::Hash for $name {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                state.write(self.name())
+            }
+        }
+
         impl std::fmt::Display for $name {
             fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
                 self.to_text(f)
@@ -194,6 +324,7 @@ macro_rules! impl_dns_name {
 /// Parse a DNS name from the wire
 ///
 pub trait FromWire<'n, 'w> {
+    #[allow(clippy::wrong_self_convention)]
     fn from_wire(&'n mut self, wire: &'w [u8], pos: usize) -> Result<usize>;
 }
 
@@ -254,6 +385,11 @@ impl<'u> Dodgy<'u> {
     }
 }
 
+pub mod case;
+pub mod compare;
+pub mod compress;
 pub mod heap;
 pub mod scratch;
+pub mod suffix;
+pub mod text;
 pub mod wire;
@@ -5,6 +5,7 @@
 //! The name and label pointers are stored inline using ArrayVec.
 
 use crate::prelude::*;
+use std::ops::Range;
 use std::str::{from_utf8, FromStr};
 
 #[derive(Debug, Default)]
@@ -75,46 +76,248 @@ impl ScratchName {
         err
     }
 
+    /// Parse a name in presentation format.
+    ///
+    /// A name without a trailing dot is treated as absolute. Returns
+    /// the position after the end of the name.
+    ///
     pub fn from_text(&mut self, text: &[u8]) -> Result<usize> {
         let dodgy = Dodgy { bytes: text };
         self.clear();
-        self.dodgy_from_text(dodgy).map_err(|err| self.clear_err(err))
+        match self.dodgy_from_text(dodgy, u8::to_ascii_lowercase) {
+            Ok((pos, _)) => Ok(pos),
+            Err(err) => Err(self.clear_err(err)),
+        }
+    }
+
+    /// Parse a name in presentation format like [`from_text()`],
+    /// but keep its original case.
+    ///
+    /// The result is not a valid [`DnsName`], so this is only for
+    /// use by [`CaseName`].
+    ///
+    /// [`from_text()`]: ScratchName::from_text
+    ///
+    pub(crate) fn parse_text_keep_case(
+        &mut self,
+        text: &[u8],
+    ) -> Result<usize> {
+        let dodgy = Dodgy { bytes: text };
+        self.clear();
+        match self.dodgy_from_text(dodgy, |&ch| ch) {
+            Ok((pos, _)) => Ok(pos),
+            Err(err) => Err(self.clear_err(err)),
+        }
+    }
+
+    /// Parse a name in presentation format, failing with
+    /// `NameRelative` if it does not have a trailing dot.
+    ///
+    pub fn from_text_absolute(&mut self, text: &[u8]) -> Result<usize> {
+        let dodgy = Dodgy { bytes: text };
+        self.clear();
+        match self.dodgy_from_text(dodgy, u8::to_ascii_lowercase) {
+            Ok((pos, true)) => Ok(pos),
+            Ok((_, false)) => Err(self.clear_err(NameRelative)),
+            Err(err) => Err(self.clear_err(err)),
+        }
+    }
+
+    /// Parse a name in presentation format, as in a zone file.
+    ///
+    /// A name without a trailing dot is relative to the `origin`,
+    /// and `@` on its own means the `origin` itself.
+    ///
+    pub fn from_text_origin<N>(
+        &mut self,
+        text: &[u8],
+        origin: &N,
+    ) -> Result<usize>
+    where
+        N: DnsLabels,
+    {
+        let dodgy = Dodgy { bytes: text };
+        self.clear();
+        let (pos, absolute) = self
+            .dodgy_from_text(dodgy, u8::to_ascii_lowercase)
+            .map_err(|err| self.clear_err(err))?;
+        if pos == 1 && text[0] == b'@' {
+            self.from_labels(origin)?;
+        } else if !absolute {
+            self.append_name(origin).map_err(|err| self.clear_err(err))?;
+        }
+        Ok(pos)
+    }
+
+    /// Copy any kind of name into the `ScratchName`, squashing it to
+    /// lower case.
+    ///
+    /// The name must not be empty.
+    ///
+    pub fn from_labels<N>(&mut self, name: &N) -> Result<()>
+    where
+        N: DnsLabels,
+    {
+        self.clear();
+        if name.labs() == 0 {
+            return Err(NameSyntax);
+        }
+        self.add_labels(name, 0..name.labs()).map_err(|err| self.clear_err(err))
+    }
+
+    /// Add a `label` to the start of the name.
+    ///
+    /// The label must not be empty, because only the root label is
+    /// empty, and the name must not be empty, because then it has no
+    /// root label. On error, the name is unchanged.
+    ///
+    pub fn prepend_label(&mut self, label: &[u8]) -> Result<()> {
+        match label.len() {
+            0 => return Err(NameSyntax),
+            _ if self.labs() == 0 => return Err(NameSyntax),
+            llen if llen > MAX_LLEN => return Err(NameLength),
+            llen => self.check_room(1, 1 + llen)?,
+        }
+        let old = std::mem::take(self);
+        self.add_label(Dodgy { bytes: label }, 0, label.len() as u8)?;
+        self.add_labels(&old, 0..old.labs())
+    }
+
+    /// Replace the root label at the end of the name with the
+    /// `origin`, so that it becomes a subdomain of the `origin`.
+    ///
+    /// Both names must be non-empty. On error, the name is unchanged.
+    ///
+    pub fn append_name<N>(&mut self, origin: &N) -> Result<()>
+    where
+        N: DnsLabels,
+    {
+        if self.labs() == 0 || origin.labs() == 0 {
+            return Err(NameSyntax);
+        }
+        self.check_room(origin.labs() - 1, origin.nlen() - 1)?;
+        self.lpos.pop();
+        self.name.pop();
+        self.add_labels(origin, 0..origin.labs())
+    }
+
+    /// Set the name to the `left` name (without its root label)
+    /// followed by the `right` name.
+    ///
+    /// Both names must be non-empty. On error, the name is cleared.
+    ///
+    pub fn concat<A, B>(&mut self, left: &A, right: &B) -> Result<()>
+    where
+        A: DnsLabels,
+        B: DnsLabels,
+    {
+        self.clear();
+        if left.labs() == 0 || right.labs() == 0 {
+            return Err(NameSyntax);
+        }
+        let labs = left.labs() + right.labs() - 1;
+        self.check_room(labs, left.nlen() + right.nlen() - 1)?;
+        self.add_labels(left, 0..left.labs() - 1)?;
+        self.add_labels(right, 0..right.labs())
+    }
+
+    /// Remove `count` labels from the start of the name.
+    ///
+    /// The root label is never removed, so if `count` is too large
+    /// the result is the root name.
+    ///
+    pub fn strip_labels(&mut self, count: usize) {
+        let old = std::mem::take(self);
+        let labs = old.labs();
+        // does not fail because the name can only shrink
+        self.add_labels(&old, count.min(labs.saturating_sub(1))..labs).unwrap();
+    }
+
+    /// Fail if the name would be too long after adding more `labs`
+    /// and `nlen` octets.
+    ///
+    fn check_room(&self, labs: usize, nlen: usize) -> Result<()> {
+        if self.labs() + labs > MAX_LABS || self.nlen() + nlen > MAX_NAME {
+            Err(NameLength)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn add_labels<N>(&mut self, name: &N, labs: Range<usize>) -> Result<()>
+    where
+        N: DnsLabels,
+    {
+        for lab in labs {
+            let label = name.label(lab).unwrap();
+            let llen = label.len().try_into()?; // u8 > MAX_LLEN
+            self.add_label(Dodgy { bytes: label }, 0, llen)?;
+        }
+        Ok(())
     }
 
     fn add_label(&mut self, dodgy: Dodgy, rpos: usize, llen: u8) -> Result<()> {
+        self.add_label_with(dodgy, rpos, llen, u8::to_ascii_lowercase)
+    }
+
+    fn add_label_with<F>(
+        &mut self,
+        dodgy: Dodgy,
+        rpos: usize,
+        llen: u8,
+        fold: F,
+    ) -> Result<()>
+    where
+        F: Fn(&u8) -> u8,
+    {
         let wpos = self.nlen().try_into()?; // u8 > MAX_NAME
         self.lpos.try_push(wpos)?;
         self.name.try_push(llen)?;
         for i in 0..llen as usize {
-            self.name.try_push(dodgy.get(rpos + i)?.to_ascii_lowercase())?;
+            self.name.try_push(fold(&dodgy.get(rpos + i)?))?;
         }
         Ok(())
     }
 
-    fn dodgy_from_text(&mut self, dodgy: Dodgy) -> Result<usize> {
+    /// Returns the position after the name, and whether it was
+    /// absolute (with a trailing dot)
+    ///
+    fn dodgy_from_text<F>(
+        &mut self,
+        dodgy: Dodgy,
+        fold: F,
+    ) -> Result<(usize, bool)>
+    where
+        F: Fn(&u8) -> u8,
+    {
         let mut label = ArrayVec::<u8, MAX_LLEN>::new();
         let mut root = 0;
         let mut pos = 0;
-        while label_from_text(&mut label, dodgy, &mut pos)? {
+        let mut absolute = false;
+        while let Some(dot) = label_from_text(&mut label, dodgy, &mut pos)? {
             let llen = label.len().try_into()?; // u8 > MAX_LLEN
             let sound = Dodgy { bytes: label.as_slice() };
-            self.add_label(sound, 0, llen)?;
+            self.add_label_with(sound, 0, llen, &fold)?;
             root += (llen == 0) as usize;
+            absolute = dot;
         }
         if root > 1 || (root > 0 && self.labs() > 1) || self.labs() == 0 {
             return Err(NameSyntax);
         } else if root == 0 {
             self.add_label(Dodgy { bytes: &[] }, 0, 0)?;
         }
-        Ok(pos)
+        Ok((pos, absolute))
     }
 }
 
+/// Returns `None` if there are no more labels, otherwise whether the
+/// label ended with a dot.
+///
 fn label_from_text(
     label: &mut ArrayVec<u8, MAX_LLEN>,
     dodgy: Dodgy,
     pos: &mut usize,
-) -> Result<bool> {
+) -> Result<Option<bool>> {
     label.clear();
     while let Ok(byte) = dodgy.get(*pos) {
         *pos += 1;
@@ -137,15 +340,20 @@ fn label_from_text(
             // terminated by RFC 1035 zone file special characters
             b'\n' | b'\r' | b'\t' | b' ' | b';' | b'(' | b')' => {
                 *pos -= 1; // unget terminator
-                return Ok(!label.is_empty());
+                break;
             }
             // always add a label when we see a delimiter
-            b'.' => return Ok(true),
+            b'.' => return Ok(Some(true)),
             // everything else
             _ => label.try_push(byte)?,
         }
     }
-    Ok(!label.is_empty())
+    // no more labels, or a last label without a trailing dot
+    if label.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(false))
+    }
 }
 
 #[cfg(test)]
@@ -160,4 +368,49 @@ mod test {
         assert_eq!("dotat.at", format!("{}", name));
         Ok(())
     }
+
+    #[test]
+    fn build() -> Result<()> {
+        let mut name = ScratchName::new();
+        name.from_text(b"www")?;
+        let origin = HeapName::try_from("Dotat.AT")?;
+        name.append_name(&origin)?;
+        assert_eq!("www.dotat.at", format!("{}", name));
+        name.prepend_label(b"_DMARC")?;
+        assert_eq!("_dmarc.www.dotat.at", format!("{}", name));
+        name.strip_labels(2);
+        assert_eq!("dotat.at", format!("{}", name));
+        name.strip_labels(5);
+        assert_eq!(".", format!("{}", name));
+        assert!(name.prepend_label(b"").is_err());
+        assert!(name.prepend_label(&[b'x'; MAX_LLEN + 1]).is_err());
+        let mut wire = Vec::new();
+        for _ in 0..3 {
+            wire.push(MAX_LLEN as u8);
+            wire.extend_from_slice(&[b'x'; MAX_LLEN]);
+        }
+        wire.push(0);
+        let long = HeapName::try_from(&wire[..])?;
+        name.from_labels(&long)?;
+        assert_eq!(name.nlen(), 193);
+        assert!(matches!(name.append_name(&long), Err(NameLength)));
+        assert_eq!(name.nlen(), 193);
+        name.prepend_label(&[b'x'; 61])?;
+        assert_eq!(name.nlen(), MAX_NAME);
+        assert!(matches!(name.prepend_label(b"x"), Err(NameLength)));
+        let mut empty = ScratchName::new();
+        assert!(matches!(empty.prepend_label(b"www"), Err(NameSyntax)));
+        assert_eq!(empty.labs(), 0);
+        assert!(matches!(empty.append_name(&origin), Err(NameSyntax)));
+        assert!(matches!(name.append_name(&empty), Err(NameSyntax)));
+        assert_eq!(name.nlen(), MAX_NAME);
+        assert!(matches!(HeapName::concat(&origin, &empty), Err(NameSyntax)));
+        assert!(matches!(HeapName::concat(&empty, &origin), Err(NameSyntax)));
+        assert!(HeapName::prepend_label(b"www", &empty).is_err());
+        assert!(HeapName::from_text_origin("@", &empty).is_err());
+        let www = HeapName::prepend_label(b"www", &origin)?;
+        let both = HeapName::concat(&www, &origin)?;
+        assert_eq!("www.dotat.at.dotat.at", format!("{}", both));
+        Ok(())
+    }
 }
@@ -0,0 +1,169 @@
+//! Borrowed parts of a DNS name
+//! ============================
+//!
+//! These types are returned by the hierarchy methods of the
+//! [`DnsLabels`] trait. They refer to the original name instead of
+//! copying it, so they work the same for every kind of name.
+
+use crate::prelude::*;
+use std::ops::Range;
+
+/// A DNS name with some labels removed from the left
+///
+/// Returned by [`DnsLabels::ancestor()`], [`DnsLabels::parent()`],
+/// and [`DnsLabels::common_ancestor()`]. It is itself a
+/// [`DnsLabels`] name, so it can be compared with other names or
+/// copied into a [`ScratchName`] or [`HeapName`].
+///
+#[derive(Debug)]
+pub struct Suffix<'n, N> {
+    name: &'n N,
+    skip: usize,
+    nlen: usize,
+}
+
+impl<'n, N> Suffix<'n, N>
+where
+    N: DnsLabels,
+{
+    /// Remove `skip` labels from the left of the `name`.
+    ///
+    /// Returns `None` if that would remove the root label.
+    ///
+    pub fn new(name: &'n N, skip: usize) -> Option<Self> {
+        if skip < name.labs() {
+            Some(Suffix::from_parts(name, skip))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn from_parts(name: &'n N, skip: usize) -> Self {
+        let nlen = (skip..name.labs())
+            .filter_map(|lab| name.label(lab))
+            .map(|label| 1 + label.len())
+            .sum();
+        Suffix { name, skip, nlen }
+    }
+}
+
+impl<N> DnsLabels for Suffix<'_, N>
+where
+    N: DnsLabels,
+{
+    fn labs(&self) -> usize {
+        self.name.labs() - self.skip
+    }
+
+    fn nlen(&self) -> usize {
+        self.nlen
+    }
+
+    fn label(&self, lab: usize) -> Option<&[u8]> {
+        self.name.label(lab + self.skip)
+    }
+}
+
+impl<N, Other> PartialEq<Other> for Suffix<'_, N>
+where
+    N: DnsLabels,
+    Other: DnsLabels,
+{
+    fn eq(&self, other: &Other) -> bool {
+        cmp_any_names(self, other) == Ordering::Equal
+    }
+}
+
+impl<N> Eq for Suffix<'_, N> where N: DnsLabels {}
+
+impl<N> std::hash::Hash for Suffix<'_, N>
+where
+    N: DnsLabels,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        hash_any_name(self, state)
+    }
+}
+
+impl<N> std::fmt::Display for Suffix<'_, N>
+where
+    N: DnsLabels,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.to_text(f)
+    }
+}
+
+/// An iterator over some of the labels of a DNS name, from left to
+/// right.
+///
+/// Returned by [`DnsLabels::relativize()`]. This is not a
+/// [`DnsLabels`] name because it does not end with the root.
+///
+#[derive(Debug)]
+pub struct Labels<'n, N> {
+    name: &'n N,
+    labs: Range<usize>,
+}
+
+impl<'n, N> Labels<'n, N>
+where
+    N: DnsLabels,
+{
+    pub(crate) fn new(name: &'n N, labs: Range<usize>) -> Self {
+        Labels { name, labs }
+    }
+}
+
+impl<'n, N> Iterator for Labels<'n, N>
+where
+    N: DnsLabels,
+{
+    type Item = &'n [u8];
+    fn next(&mut self) -> Option<&'n [u8]> {
+        self.name.label(self.labs.next()?)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.labs.size_hint()
+    }
+}
+
+impl<N> ExactSizeIterator for Labels<'_, N> where N: DnsLabels {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() -> Result<()> {
+        let wire = b"\x03WWW\x05Dotat\x02AT\x00";
+        let mut www = WireLabels::<u8>::new();
+        www.from_wire(wire, 0)?;
+        let dotat = HeapName::try_from("dotat.at")?;
+        let mail = HeapName::try_from("mail.dotat.at")?;
+        let root = HeapName::try_from(".")?;
+        assert_eq!(www.parent().unwrap(), dotat);
+        assert_eq!("Dotat.AT", format!("{}", www.parent().unwrap()));
+        assert_eq!(www.ancestor(3).unwrap(), root);
+        assert!(www.ancestor(4).is_none());
+        assert!(root.parent().is_none());
+        assert!(www.is_subdomain_of(&dotat));
+        assert!(www.is_subdomain_of(&www));
+        assert!(!dotat.is_subdomain_of(&www));
+        assert!(!www.is_subdomain_of(&mail));
+        assert_eq!(www.common_labs(&mail), 3);
+        assert_eq!(www.common_ancestor(&mail), dotat);
+        assert_eq!(www.common_ancestor(&root).labs(), 1);
+        let rel: Vec<&[u8]> = www.relativize(&dotat).unwrap().collect();
+        assert_eq!(rel, [b"WWW"]);
+        assert_eq!(www.relativize(&www).unwrap().len(), 0);
+        assert!(dotat.relativize(&www).is_none());
+        let mut scratch = ScratchName::new();
+        scratch.from_labels(&www.parent().unwrap())?;
+        assert_eq!(scratch, dotat);
+        let heap = HeapName::from(www.parent().unwrap());
+        assert_eq!(heap.name(), b"\x05dotat\x02at\x00");
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+//! Presentation format options
+//! ===========================
+//!
+//! By default, [`DnsLabels::to_text()`] writes names without a
+//! trailing dot (except for the root and top-level names), and
+//! escapes awkward characters in the RFC 1035 style. A [`TextFormat`]
+//! passed to [`DnsLabels::fmt_text()`] or [`DnsLabels::write_text()`]
+//! can change that.
+
+use crate::prelude::*;
+use std::fmt::Write;
+
+type FmtResult = std::fmt::Result;
+
+/// Maximum length of a name in presentation format
+///
+/// Every byte of the name expands to at most four characters, and
+/// each label length becomes a dot.
+///
+pub const MAX_TEXT: usize = MAX_NAME * 4;
+
+/// Options for writing a name in presentation format
+///
+/// The origin can be any kind of [`DnsLabels`] name.
+///
+#[derive(Debug)]
+pub struct TextFormat<'o, O = HeapName> {
+    /// Always write a dot after the last label of an absolute name.
+    /// Names written relative to the `origin` never get a trailing
+    /// dot, because that would make them absolute.
+    pub trailing_dot: bool,
+    /// How to write characters that are not letters, digits or hyphens
+    pub escape: TextEscape,
+    /// Write subdomains of this name relative to it, and the name
+    /// itself as `@`. Other names are written with a trailing dot.
+    pub origin: Option<&'o O>,
+}
+
+impl TextFormat<'_> {
+    /// The default format, without an origin
+    ///
+    /// Unlike `TextFormat::default()`, this does not need the type of
+    /// the origin to be spelled out.
+    ///
+    pub fn new() -> Self {
+        TextFormat::default()
+    }
+}
+
+impl<O> Default for TextFormat<'_, O> {
+    fn default() -> Self {
+        TextFormat {
+            trailing_dot: false,
+            escape: TextEscape::default(),
+            origin: None,
+        }
+    }
+}
+
+impl<O> Clone for TextFormat<'_, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<O> Copy for TextFormat<'_, O> {}
+
+/// How to escape characters when writing a name
+///
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TextEscape {
+    /// Write `*` and `_` as they are, other printable characters
+    /// as `\X`, and the rest as `\DDD`
+    #[default]
+    Character,
+    /// Write `*` and `_` as they are, and the rest as `\DDD`
+    Decimal,
+    /// Write everything except letters, digits and hyphens as `\DDD`
+    Hostname,
+}
+
+impl TextEscape {
+    pub(crate) fn write_label<W>(self, out: &mut W, label: &[u8]) -> FmtResult
+    where
+        W: Write,
+    {
+        use TextEscape::*;
+        for &byte in label.iter() {
+            match (self, byte) {
+                (_, b'-' | b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z') => {
+                    out.write_char(byte as char)?
+                }
+                (Character | Decimal, b'*' | b'_') => {
+                    out.write_char(byte as char)?
+                }
+                (Character, b'!'..=b'~') => write!(out, "\\{}", byte as char)?,
+                // RFC 1035 peculiar decimal escapes
+                _ => write!(out, "\\{:03}", byte)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Adapter for writing text into a byte buffer without allocating
+///
+pub(crate) struct TextBuffer<'b> {
+    pub(crate) buf: &'b mut [u8],
+    pub(crate) pos: usize,
+}
+
+impl Write for TextBuffer<'_> {
+    fn write_str(&mut self, text: &str) -> FmtResult {
+        let end = self.pos + text.len();
+        let out = self.buf.get_mut(self.pos..end).ok_or(std::fmt::Error)?;
+        out.copy_from_slice(text.as_bytes());
+        self.pos = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn text<O>(name: &HeapName, format: &TextFormat<O>) -> Result<String>
+    where
+        O: DnsLabels,
+    {
+        let mut buf = [0u8; MAX_TEXT];
+        let len = name.write_text(&mut buf, format)?;
+        Ok(std::str::from_utf8(&buf[..len])?.to_owned())
+    }
+
+    #[test]
+    fn test() -> Result<()> {
+        let name = HeapName::try_from("a\\.b_c*\\009.dotat.at")?;
+        let origin = HeapName::try_from("dotat.at")?;
+        let other = HeapName::try_from("dotat.org")?;
+        let mut format = TextFormat::default();
+        assert_eq!(text(&name, &format)?, format!("{}", name));
+        assert_eq!(text(&name, &format)?, "a\\.b_c*\\009.dotat.at");
+        format.trailing_dot = true;
+        assert_eq!(text(&name, &format)?, "a\\.b_c*\\009.dotat.at.");
+        format.escape = TextEscape::Decimal;
+        assert_eq!(text(&name, &format)?, "a\\046b_c*\\009.dotat.at.");
+        format.escape = TextEscape::Hostname;
+        assert_eq!(text(&name, &format)?, "a\\046b\\095c\\042\\009.dotat.at.");
+        format.trailing_dot = false;
+        format.origin = Some(&origin);
+        assert_eq!(text(&name, &format)?, "a\\046b\\095c\\042\\009");
+        assert_eq!(text(&origin, &format)?, "@");
+        assert_eq!(text(&other, &format)?, "dotat.org.");
+        let root = HeapName::try_from(".")?;
+        assert_eq!(text(&root, &TextFormat::new())?, ".");
+        assert_eq!(text(&root, &format)?, ".");
+        let mut small = [0u8; 8];
+        assert!(name.write_text(&mut small, &format).is_err());
+        let mut wire = WireLabels::<u8>::new();
+        wire.from_wire(b"\x05Dotat\x02AT\x00", 0)?;
+        let format = TextFormat {
+            trailing_dot: false,
+            escape: TextEscape::Hostname,
+            origin: Some(&wire),
+        };
+        assert_eq!(text(&name, &format)?, "a\\046b\\095c\\042\\009");
+        assert_eq!(text(&origin, &format)?, "@");
+        // only absolute names get a trailing dot
+        let format = TextFormat { trailing_dot: true, ..format };
+        assert_eq!(text(&name, &format)?, "a\\046b\\095c\\042\\009");
+        assert_eq!(text(&other, &format)?, "dotat.org.");
+        Ok(())
+    }
+}
@@ -41,6 +41,16 @@ where
         self.clear();
         err
     }
+
+    /// The position of a label's length byte in the wire data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the label is out of range.
+    ///
+    pub(crate) fn label_pos(&self, lab: usize) -> usize {
+        into_usize(self.lpos[lab])
+    }
 }
 
 impl<P> DnsLabels for WireLabels<'_, P>
@@ -123,45 +133,22 @@ where
     }
 }
 
-impl<P> std::fmt::Display for WireLabels<'_, P>
+impl<P> std::hash::Hash for WireLabels<'_, P>
 where
     P: Copy + TryFrom<usize> + Into<usize>,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        self.to_text(f)
-    }
-}
-
-fn cmp_any_labels(aa: &[u8], bb: &[u8]) -> Ordering {
-    for chr in 0.. {
-        let a = &aa.get(chr).map(|a| a.to_ascii_lowercase());
-        let b = &bb.get(chr).map(|b| b.to_ascii_lowercase());
-        match a.cmp(b) {
-            Ordering::Equal if a.is_none() && b.is_none() => break,
-            Ordering::Equal => continue,
-            ne => return ne,
-        }
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        hash_any_name(self, state)
     }
-    Ordering::Equal
 }
 
-fn cmp_any_names<A, B>(aa: &A, bb: &B) -> Ordering
+impl<P> std::fmt::Display for WireLabels<'_, P>
 where
-    A: DnsLabels,
-    B: DnsLabels,
+    P: Copy + TryFrom<usize> + Into<usize>,
 {
-    for lab in 0.. {
-        match (aa.rlabel(lab), bb.rlabel(lab)) {
-            (None, None) => break,
-            (None, Some(_)) => return Ordering::Less,
-            (Some(_), None) => return Ordering::Greater,
-            (Some(a), Some(b)) => match cmp_any_labels(a, b) {
-                Ordering::Equal => continue,
-                ne => return ne,
-            },
-        }
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.to_text(f)
     }
-    Ordering::Equal
 }
 
 fn from_usize<P>(pos: usize) -> Result<P>
@@ -26,6 +26,8 @@ pub enum Error {
     LabelType(u8),
     #[error("DNS name is too long")]
     NameLength,
+    #[error("DNS name is not absolute")]
+    NameRelative,
     #[error("Syntax error in domain name")]
     NameSyntax,
     #[error("DNS name has trailing junk")]
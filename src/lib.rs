@@ -1,12 +1,15 @@
 pub use crate::bmpvec::*;
 pub use crate::dnsname::*;
 pub use crate::triebits::*;
+pub use crate::typevec::*;
 
 pub mod bmpvec;
 pub mod dnsname;
 pub mod error;
+pub mod nsec3;
 pub mod qptrie;
 pub mod triebits;
+pub mod typevec;
 
 #[cfg(any(test, feature = "test"))]
 pub mod test;
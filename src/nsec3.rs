@@ -0,0 +1,161 @@
+//! NSEC3 hashed owner names
+//! ========================
+//!
+//! RFC 5155 hashes a name by applying SHA-1 to its canonical wire
+//! format followed by the salt, then repeatedly hashing the result
+//! (again followed by the salt) for the given number of additional
+//! iterations. The hashed owner name's first label is the hash in
+//! unpadded base32hex.
+//!
+//! SHA-1 is implemented here, because it is the only hash algorithm
+//! defined for NSEC3 and it is small.
+
+use crate::prelude::*;
+
+/// Length of an NSEC3 SHA-1 hash, in octets
+pub const NSEC3_HASH_LEN: usize = 20;
+
+/// Space for the hash input: a name or a hash, plus a salt
+const NSEC3_INPUT: usize = MAX_NAME + 255;
+
+/// Calculate the NSEC3 hash of a `name`
+///
+/// The name is already in canonical (lower case) form because it is
+/// a [`DnsName`]. The `salt` can be at most 255 bytes.
+///
+pub fn nsec3_hash<N>(
+    name: &N,
+    salt: &[u8],
+    iterations: u16,
+) -> Result<[u8; NSEC3_HASH_LEN]>
+where
+    N: DnsName,
+{
+    let mut input = ArrayVec::<u8, NSEC3_INPUT>::new();
+    input.try_extend_from_slice(name.name())?;
+    input.try_extend_from_slice(salt)?;
+    let mut hash = sha1(&input);
+    for _ in 0..iterations {
+        input.clear();
+        input.try_extend_from_slice(&hash)?;
+        input.try_extend_from_slice(salt)?;
+        hash = sha1(&input);
+    }
+    Ok(hash)
+}
+
+/// Encode a `hash` as an unpadded lower-case base32hex label
+///
+/// Fails if the hash is too long to fit in a label.
+///
+pub fn base32hex(hash: &[u8]) -> Result<ArrayVec<u8, MAX_LLEN>> {
+    const DIGITS: &[u8; 32] = b"0123456789abcdefghijklmnopqrstuv";
+    let mut label = ArrayVec::new();
+    let mut acc = 0u16;
+    let mut bits = 0;
+    for &byte in hash {
+        acc = acc << 8 | byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            label.try_push(DIGITS[(acc >> bits) as usize & 0x1F])?;
+        }
+    }
+    if bits > 0 {
+        label.try_push(DIGITS[(acc << (5 - bits)) as usize & 0x1F])?;
+    }
+    Ok(label)
+}
+
+/// FIPS 180-4 SHA-1, for short messages
+fn sha1(msg: &[u8]) -> [u8; NSEC3_HASH_LEN] {
+    let mut h: [u32; 5] =
+        [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let bitlen = (msg.len() as u64) * 8;
+    let tail = msg.chunks_exact(64).remainder();
+    let mut pad = [0u8; 128];
+    pad[..tail.len()].copy_from_slice(tail);
+    pad[tail.len()] = 0x80;
+    let padlen = if tail.len() < 56 { 64 } else { 128 };
+    pad[padlen - 8..padlen].copy_from_slice(&bitlen.to_be_bytes());
+    for block in msg.chunks_exact(64).chain(pad[..padlen].chunks_exact(64)) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes(word.try_into().unwrap());
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &wi) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let t = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(wi);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = t;
+        }
+        for (hi, x) in h.iter_mut().zip([a, b, c, d, e]) {
+            *hi = hi.wrapping_add(x);
+        }
+    }
+    let mut out = [0u8; NSEC3_HASH_LEN];
+    for (chunk, hi) in out.chunks_exact_mut(4).zip(h.iter()) {
+        chunk.copy_from_slice(&hi.to_be_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn sha1() {
+        assert_eq!(
+            hex(&super::sha1(b"abc")),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        let msg = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+        assert_eq!(
+            hex(&super::sha1(&msg[..])),
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+        );
+        assert_eq!(
+            hex(&super::sha1(&[b'a'; 1000][..])),
+            "291e9a6c66994949b57ba5e650361e98fc36b1ba"
+        );
+    }
+
+    #[test]
+    fn nsec3() -> Result<()> {
+        // examples from RFC 5155 appendix A
+        let salt = [0xAA, 0xBB, 0xCC, 0xDD];
+        for &(text, hash) in &[
+            ("example", "0p9mhaveqvm6t7vbl5lop2u3t2rp3tom"),
+            ("a.example", "35mthgpgcu1qg68fab165klnsnk3dpvl"),
+            ("ns1.example", "2t7b4g4vsa5smi47k61mv5bv1a22bojr"),
+            ("*.w.example", "r53bq7cc2uvmubfu5ocmm6pers9tk9en"),
+        ] {
+            let name = HeapName::try_from(text)?;
+            let label = base32hex(&nsec3_hash(&name, &salt, 12)?)?;
+            assert_eq!(label.as_slice(), hash.as_bytes());
+        }
+        Ok(())
+    }
+}
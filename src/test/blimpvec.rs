@@ -12,6 +12,12 @@ pub struct BlimpVec<T> {
     vec: Vec<Option<T>>,
 }
 
+impl<T> Default for BlimpVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> BlimpVec<T> {
     pub fn new() -> BlimpVec<T> {
         let mut vec = Vec::new();
@@ -46,10 +52,7 @@ impl<T> BlimpVec<T> {
     }
 
     pub fn values(&self) -> impl Iterator<Item = &T> {
-        self.vec
-            .iter()
-            .filter(|elem| elem.is_some())
-            .map(|elem| elem.as_ref().unwrap())
+        self.vec.iter().filter_map(|elem| elem.as_ref())
     }
 
     pub fn contains<N>(&self, pos: N) -> bool
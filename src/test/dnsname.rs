@@ -1,12 +1,22 @@
 use crate::test::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Hash a name with the standard library's default hasher
+///
+pub fn hash<N: Hash>(name: &N) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    hasher.finish()
+}
 
 pub fn exercise_wire(wire: &[u8]) {
-    if wire.len() < 1 {
+    if wire.is_empty() {
         return;
     }
     let start = wire[0] as usize;
     let mut wire_labels = WireLabels::<u16>::new();
-    if let Err(_) = wire_labels.from_wire(wire, start) {
+    if wire_labels.from_wire(wire, start).is_err() {
         return;
     }
     let mut scratch1 = ScratchName::new();
@@ -16,11 +26,17 @@ pub fn exercise_wire(wire: &[u8]) {
         panic!("unexpected error {:#?}", err);
     }
     assert_eq!(wire_labels, scratch1);
+    let full = full_compare(&wire_labels, &scratch1);
+    assert_eq!(full.relation, NameRelation::Equal);
+    assert_eq!(full.labs, scratch1.labs());
     assert!(wire_labels <= scratch1);
     assert!(wire_labels >= scratch1);
     let heap1 = HeapName::from(&wire_labels);
     let heap2 = HeapName::from(&scratch1);
     assert_eq!(heap1, heap2);
+    assert_eq!(hash(&wire_labels), hash(&scratch1));
+    assert_eq!(hash(&heap1), hash(&scratch1));
+    assert_eq!(hash(&CaseName::try_from(&wire_labels).unwrap()), hash(&heap2));
     assert!(heap1 <= heap2);
     assert!(heap1 >= heap2);
     assert!(wire_labels <= heap2);
@@ -45,6 +61,14 @@ pub fn exercise_wire(wire: &[u8]) {
     let heap4 = HeapName::try_from(scratch1.name()).unwrap();
     assert_eq!(scratch1, heap4);
     assert_eq!(heap3, heap4);
+    let mut msg = Vec::new();
+    let mut compressor = Compressor::new();
+    compressor.write_name(&mut msg, &wire_labels).unwrap();
+    let start = msg.len();
+    compressor.write_name(&mut msg, &heap4).unwrap();
+    let mut scratch3 = ScratchName::new();
+    assert_eq!(scratch3.from_wire(&msg, start).unwrap(), msg.len());
+    assert_eq!(scratch1, scratch3);
 }
 
 pub fn exercise_text(text: &[u8]) {
@@ -62,6 +86,11 @@ pub fn exercise_text(text: &[u8]) {
         Err(err) => panic!("unexpected error {:#?}", err),
     };
     assert_eq!(scratch, heap);
+    let mut absolute = ScratchName::new();
+    if let Ok(end) = absolute.from_text_absolute(text) {
+        assert_eq!(end, len);
+        assert_eq!(absolute, scratch);
+    }
 }
 
 #[cfg(test)]
@@ -2,4 +2,4 @@ pub use crate::prelude::*;
 pub use crate::test::bmpvec;
 pub use crate::test::dnsname;
 pub use crate::test::triebits;
-pub use arbitrary::*;
+pub use arbitrary::{Arbitrary, Unstructured};
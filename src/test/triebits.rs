@@ -9,10 +9,10 @@ pub struct Vecs {
 pub fn exercise_vecs(vecs: Vecs) {
     let mut scratch1 = ScratchName::new();
     let mut scratch2 = ScratchName::new();
-    if let Err(_) = scratch1.from_wire(&vecs.one[..], 0) {
+    if scratch1.from_wire(&vecs.one[..], 0).is_err() {
         return;
     }
-    if let Err(_) = scratch2.from_wire(&vecs.two[..], 0) {
+    if scratch2.from_wire(&vecs.two[..], 0).is_err() {
         return;
     }
     let scratch_ord = scratch1.cmp(&scratch2);
@@ -33,7 +33,7 @@ pub fn exercise_vecs(vecs: Vecs) {
 }
 
 // make a vec more likely to look like a wire format DNS name
-fn nominate(v: &mut Vec<u8>) {
+fn nominate(v: &mut [u8]) {
     let mut pos = 0;
     let mut len = 0;
     while pos < MAX_NAME && pos < v.len() {
@@ -102,7 +102,7 @@ impl TrieName {
 /// A few non-hostname characters (between '-' and the digits, and
 /// between '_' and lower case letters) are treated the same way as
 /// hostname characters so that the escaped ranges are simpler.
-
+///
 const fn gen_byte_to_bits() -> [(u8, u8); 256] {
     let mut bit_one = SHIFT_BITMAP;
     let mut bit_two = SHIFT_BITMAP;
@@ -175,17 +175,14 @@ mod test {
             let upper = lower - 32;
             assert_eq!(BYTE_TO_BITS[upper], BYTE_TO_BITS[lower]);
         }
-        for i in 0..=255 {
-            let (one, two) = BYTE_TO_BITS[i];
+        for &(one, two) in BYTE_TO_BITS.iter() {
             assert!(one >= SHIFT_BITMAP);
             assert!(one < SHIFT_OFFSET);
             assert!(two >= SHIFT_BITMAP || two == 0);
             assert!(two < SHIFT_OFFSET);
         }
-        for i in 0..=254 {
-            let j = i + 1;
-            let (ilo, ihi) = BYTE_TO_BITS[i];
-            let (jlo, jhi) = BYTE_TO_BITS[j];
+        for (i, pair) in BYTE_TO_BITS.windows(2).enumerate() {
+            let ((ilo, ihi), (jlo, jhi)) = (pair[0], pair[1]);
             assert!(ilo <= jlo || i as u8 == b'Z');
             if ilo == jlo {
                 assert!(ihi < jhi);
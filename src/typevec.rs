@@ -0,0 +1,167 @@
+//! Values keyed by RR type
+//! =======================
+//!
+//! A [`TypeVec`] holds the values stored at one owner name, such as
+//! its RRsets, keyed by their 16-bit RR type. It is intended to be
+//! used as the leaf value of a `DnsTrie`.
+//!
+//! Most names have only a few types, so like a
+//! [`BmpVec`][crate::BmpVec] the elements are kept in a single
+//! exactly-sized allocation, sorted by type, and the storage is
+//! reallocated whenever an element is inserted or removed.
+//!
+//! The types present at a name can be encoded as an NSEC type bitmap,
+//! as described in RFC 4034 section 4.1.2.
+
+/// A compact sorted map from RR type to value.
+///
+pub struct TypeVec<T> {
+    vec: Box<[(u16, T)]>,
+}
+
+impl<T> Default for TypeVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> TypeVec<T> {
+    /// Constructs a new, empty `TypeVec`.
+    pub fn new() -> TypeVec<T> {
+        TypeVec { vec: Box::default() }
+    }
+
+    /// Returns `true` if there are no types in the `TypeVec`
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns the number of types in the `TypeVec`
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Returns `true` if there is a value for the given `rrtype`
+    pub fn contains(&self, rrtype: u16) -> bool {
+        self.search(rrtype).is_ok()
+    }
+
+    /// Get a reference to the value for the given `rrtype`
+    pub fn get(&self, rrtype: u16) -> Option<&T> {
+        let i = self.search(rrtype).ok()?;
+        Some(&self.vec[i].1)
+    }
+
+    /// Get a mutable reference to the value for the given `rrtype`
+    pub fn get_mut(&mut self, rrtype: u16) -> Option<&mut T> {
+        let i = self.search(rrtype).ok()?;
+        Some(&mut self.vec[i].1)
+    }
+
+    /// Set the `val`ue for the given `rrtype`, returning the old value
+    /// if there was one.
+    ///
+    pub fn insert(&mut self, rrtype: u16, val: T) -> Option<T> {
+        match self.search(rrtype) {
+            Ok(i) => Some(std::mem::replace(&mut self.vec[i].1, val)),
+            Err(i) => {
+                let mut vec = std::mem::take(&mut self.vec).into_vec();
+                vec.insert(i, (rrtype, val));
+                self.vec = vec.into_boxed_slice();
+                None
+            }
+        }
+    }
+
+    /// Remove the value for the given `rrtype`, returning it if there
+    /// was one.
+    ///
+    pub fn remove(&mut self, rrtype: u16) -> Option<T> {
+        let i = self.search(rrtype).ok()?;
+        let mut vec = std::mem::take(&mut self.vec).into_vec();
+        let (_, old) = vec.remove(i);
+        self.vec = vec.into_boxed_slice();
+        Some(old)
+    }
+
+    /// An iterator visiting each type in ascending order
+    pub fn types(&self) -> impl Iterator<Item = u16> + '_ {
+        self.vec.iter().map(|&(rrtype, _)| rrtype)
+    }
+
+    /// An iterator visiting each value in ascending order of type
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.vec.iter().map(|(_, val)| val)
+    }
+
+    /// An iterator visiting each type and value in ascending order of type
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &T)> {
+        self.vec.iter().map(|(rrtype, val)| (*rrtype, val))
+    }
+
+    /// Encode the types as an NSEC type bitmap (RFC 4034 section 4.1.2)
+    ///
+    /// The caller is responsible for inserting the NSEC and RRSIG
+    /// types if they should appear in the bitmap.
+    ///
+    pub fn nsec_bitmap(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut types = self.types().peekable();
+        while let Some(&first) = types.peek() {
+            let window = first >> 8;
+            let mut bmp = [0u8; 32];
+            let mut len = 0;
+            while let Some(rrtype) = types.next_if(|&t| t >> 8 == window) {
+                let byte = (rrtype & 0xFF) as usize >> 3;
+                bmp[byte] |= 0x80 >> (rrtype & 7);
+                len = byte + 1;
+            }
+            out.push(window as u8);
+            out.push(len as u8);
+            out.extend_from_slice(&bmp[..len]);
+        }
+        out
+    }
+
+    fn search(&self, rrtype: u16) -> Result<usize, usize> {
+        self.vec.binary_search_by_key(&rrtype, |&(t, _)| t)
+    }
+}
+
+impl<T> std::fmt::Debug for TypeVec<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut types = TypeVec::new();
+        assert_eq!(types.insert(15, "mx"), None);
+        assert_eq!(types.insert(1, "a"), None);
+        assert_eq!(types.insert(1234, "wat"), None);
+        assert_eq!(types.insert(46, "rrsig"), None);
+        assert_eq!(types.insert(47, "nsec"), None);
+        assert_eq!(types.insert(28, "aaaa"), None);
+        assert_eq!(types.remove(28), Some("aaaa"));
+        assert_eq!(types.remove(28), None);
+        assert_eq!(types.insert(15, "MX"), Some("mx"));
+        assert_eq!(types.get(15), Some(&"MX"));
+        assert_eq!(types.len(), 5);
+        let order: Vec<u16> = types.types().collect();
+        assert_eq!(order, [1, 15, 46, 47, 1234]);
+        // example from RFC 4034 section 4.3
+        let mut bitmap = vec![0x00, 0x06, 0x40, 0x01, 0x00, 0x00, 0x00, 0x03];
+        bitmap.extend_from_slice(&[0x04, 0x1b]);
+        bitmap.extend_from_slice(&[0x00; 26]);
+        bitmap.push(0x20);
+        assert_eq!(types.nsec_bitmap(), bitmap);
+    }
+}